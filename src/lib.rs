@@ -58,6 +58,59 @@ macro_rules! count {
     // substitute an element with Unit
     // the great thing about Unit is that it takes 0 space on the stack!
     (@SUBST; $_element:expr) => { () };
+    // same trick as above, but for a list of `ident`/`tt` items rather than `expr` - an `expr`
+    // like `a + b` is many token trees, so it can't be matched by `tt`, which is why this needs
+    // its own arm (and its own substitution rule) instead of reusing the one above
+    (@ITEMS; $($item:tt),*) => {
+        <[()]>::len(&[$($crate::count![@SUBST_ITEM; $item]),*])
+    };
+    (@SUBST_ITEM; $_item:tt) => { () };
+}
+
+/// counts a list of identifiers/token-trees at compile-time, e.g. a list of enum variant names or
+/// bare idents, using the same zero-cost `count!` substitution trick
+#[macro_export]
+macro_rules! count_items {
+    ($($item:tt),*) => {
+        $crate::count![@ITEMS; $($item),*]
+    };
+    ($($item:tt,)*) => {
+        $crate::count_items![$($item),*]
+    };
+}
+
+/// builds a `#[repr(usize)] enum` where each variant is assigned the next bitmask, e.g.
+/// `indexed_enum!{ Flags { A, B, C } }` emits `A = 1 << 0`, `B = 1 << 1`, `C = 1 << 2`
+///
+/// this threads a running index through a tt-muncher accumulator, in the same substitution
+/// discipline as the `@SUBST`/`@ITEMS` arms of [`count!`]: each step appends `$variant = 1 << $idx`
+/// to the enum body being built up and increments `$idx` for the next variant, terminating once a
+/// single variant remains
+///
+/// an empty variant list, e.g. `indexed_enum!{ Empty {} }`, is its own base case and emits a plain
+/// zero-variant `enum $name {}` with no `#[repr(usize)]` discriminant to assign
+#[macro_export]
+macro_rules! indexed_enum {
+    ($name:ident { }) => {
+        // a zero-variant enum is uninhabitable by construction, so it reads as dead code
+        #[allow(dead_code)]
+        enum $name {}
+    };
+    ($name:ident { $($variant:ident),* $(,)? }) => {
+        $crate::indexed_enum! { @accum $name { } ; 0 ; $($variant),* }
+    };
+    (@accum $name:ident { $($body:tt)* } ; $idx:expr ; $variant:ident) => {
+        #[repr(usize)]
+        enum $name {
+            $($body)*
+            $variant = 1 << $idx,
+        }
+    };
+    (@accum $name:ident { $($body:tt)* } ; $idx:expr ; $variant:ident, $($rest:ident),*) => {
+        $crate::indexed_enum! {
+            @accum $name { $($body)* $variant = 1 << $idx, } ; $idx + 1 ; $($rest),*
+        }
+    };
 }
 
 #[test]
@@ -85,27 +138,66 @@ fn repeated() {
     assert_eq!(x[30], 42);
 }
 
-// not sure why this is showing an error on import?
 use std::collections::HashMap;
 
-/// used to create `HashMap` in very little code
+/// used to create a map in very little code
+///
+/// the first token selects the backing map type - `HashMap` (the default, used automatically when
+/// no map type is given) pre-sizes its capacity from the pair count via `count!`, `BTreeMap` has
+/// no capacity constructor so it falls back to `new()`, and any other map type falls back to
+/// `new()` too, so this no longer needs `HashMap` to be in scope
 ///
 /// if a key is defined more than once, the last used key will be the one used in the dictionary,
 /// the other will be overridden
 #[macro_export]
 macro_rules! dict {
-    ($($key:expr => $val:expr),*) => {{
+    (HashMap : $($key:expr => $val:expr),*) => {{
         const ELEM_COUNT: usize = $crate::count![$($key),*];
         #[allow(unused_mut)]
-        let mut hm = HashMap::with_capacity(ELEM_COUNT);
+        let mut hm = std::collections::HashMap::with_capacity(ELEM_COUNT);
         $(hm.insert($key, $val);)*
         hm
     }};
+    (HashMap : $($key:expr => $val:expr,)*) => {{
+        $crate::dict![HashMap : $($key => $val),*]
+    }};
+    (BTreeMap : $($key:expr => $val:expr),*) => {{
+        #[allow(unused_mut)]
+        let mut hm = std::collections::BTreeMap::new();
+        $(hm.insert($key, $val);)*
+        hm
+    }};
+    (BTreeMap : $($key:expr => $val:expr,)*) => {{
+        $crate::dict![BTreeMap : $($key => $val),*]
+    }};
+    ($MapTy:ty : $($key:expr => $val:expr),*) => {{
+        #[allow(unused_mut)]
+        let mut hm = <$MapTy>::new();
+        $(hm.insert($key, $val);)*
+        hm
+    }};
+    ($MapTy:ty : $($key:expr => $val:expr,)*) => {{
+        $crate::dict![$MapTy : $($key => $val),*]
+    }};
+    ($($key:expr => $val:expr),*) => {{
+        $crate::dict![HashMap : $($key => $val),*]
+    }};
     ($($key:expr => $val:expr,)*) => {{
         $crate::dict![$($key => $val),*]
     }};
 }
 
+/// shorthand for `dict!{ BTreeMap : ... }`, producing a sorted map
+#[macro_export]
+macro_rules! bdict {
+    ($($key:expr => $val:expr),*) => {{
+        $crate::dict![BTreeMap : $($key => $val),*]
+    }};
+    ($($key:expr => $val:expr,)*) => {{
+        $crate::bdict![$($key => $val),*]
+    }};
+}
+
 #[test]
 fn single_hashmap() {
     // macros can use square brackets, curly brackets or normal brackets - it literally does not
@@ -135,3 +227,328 @@ fn many_hashmap() {
     assert_eq!(hm.get("John").unwrap(), &"Sailor");
     assert_eq!(hm.get("Peter").unwrap(), &"Baker");
 }
+
+#[test]
+fn btreemap_sorted() {
+    let bm = dict! { BTreeMap : 3 => "c", 1 => "a", 2 => "b" };
+    assert_eq!(bm.into_iter().collect::<Vec<_>>(), vec![(1, "a"), (2, "b"), (3, "c")]);
+}
+
+#[test]
+fn bdict_shorthand() {
+    let bm = bdict! { 2 => "b", 1 => "a", };
+    assert_eq!(bm.into_iter().collect::<Vec<_>>(), vec![(1, "a"), (2, "b")]);
+}
+
+#[test]
+fn dict_generic_maptype_fallback() {
+    let hm = dict! {
+        std::collections::HashMap<&str, i32> : "a" => 1, "b" => 2,
+    };
+    assert_eq!(hm.len(), 2);
+}
+
+/// used to create a `HashSet` in very little code
+///
+/// if an element is defined more than once, later duplicates silently collapse into the earlier
+/// entry (`HashSet::insert` keeps the element already present), so each distinct value appears
+/// once, just as a repeated key in [`dict!`] leaves a single entry
+#[macro_export]
+macro_rules! set {
+    ($($element:expr),*) => {{
+        const ELEM_COUNT: usize = $crate::count![$($element),*];
+        #[allow(unused_mut)]
+        let mut hs = std::collections::HashSet::with_capacity(ELEM_COUNT);
+        $(hs.insert($element);)*
+        hs
+    }};
+    ($($element:expr,)*) => {{
+        $crate::set![$($element),*]
+    }};
+}
+
+/// used to create a `BTreeSet` in very little code
+///
+/// `BTreeSet` has no capacity constructor, so unlike [`set!`] this just starts from `new()`; see
+/// [`set!`] for what happens when an element is repeated
+#[macro_export]
+macro_rules! bset {
+    ($($element:expr),*) => {{
+        #[allow(unused_mut)]
+        let mut bs = std::collections::BTreeSet::new();
+        $(bs.insert($element);)*
+        bs
+    }};
+    ($($element:expr,)*) => {{
+        $crate::bset![$($element),*]
+    }};
+}
+
+#[test]
+fn empty_hashset() {
+    let hs: std::collections::HashSet<u32> = set! {};
+    assert_eq!(hs.len(), 0);
+}
+
+#[test]
+fn single_hashset() {
+    let hs = set! { 42 };
+    assert_eq!(hs.len(), 1);
+    assert!(hs.contains(&42));
+}
+
+#[test]
+fn many_hashset_dedup() {
+    let hs = set! { 1, 2, 2, 3, };
+    assert_eq!(hs.len(), 3);
+}
+
+#[test]
+fn empty_btreeset() {
+    let bs: std::collections::BTreeSet<u32> = bset! {};
+    assert_eq!(bs.len(), 0);
+}
+
+#[test]
+fn many_btreeset_sorted() {
+    let bs = bset! { 3, 1, 2 };
+    assert_eq!(bs.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+}
+
+/// unwraps a `Some`, or returns `None` from the enclosing function
+///
+/// this cannot be a regular function because it performs an early `return` from the *caller*, not
+/// from itself; `$e` is only evaluated once, so it's safe to use with expressions that have side
+/// effects
+///
+/// an optional `, else $else` arm lets the caller run something other than `return None` when the
+/// value is missing, e.g. `try_opt!(v, else return Vec::new())`
+#[macro_export]
+macro_rules! try_opt {
+    ($e:expr) => {
+        match $e {
+            Some(x) => x,
+            None => return None,
+        }
+    };
+    ($e:expr, else $else:expr) => {
+        match $e {
+            Some(x) => x,
+            None => $else,
+        }
+    };
+}
+
+/// unwraps an `Ok`, or returns `Err(e.into())` from the enclosing function
+///
+/// like [`try_opt!`], this performs an early `return` from the *caller*, and only evaluates `$e`
+/// once
+#[macro_export]
+macro_rules! try_res {
+    ($e:expr) => {
+        match $e {
+            Ok(x) => x,
+            Err(e) => return Err(e.into()),
+        }
+    };
+}
+
+#[cfg(test)]
+fn checked_chain(a: u32, b: u32, c: u32) -> Option<u32> {
+    let bc = try_opt!(b.checked_mul(c));
+    let sum = try_opt!(a.checked_add(bc));
+    Some(sum)
+}
+
+#[test]
+fn try_opt_overflow_chain() {
+    assert_eq!(checked_chain(1, 2, 3), Some(7));
+    assert_eq!(checked_chain(u32::MAX, 2, 3), None);
+}
+
+#[cfg(test)]
+fn checked_chain_or_default(a: u32, b: u32, c: u32) -> Vec<u32> {
+    let bc = try_opt!(b.checked_mul(c), else return Vec::new());
+    let sum = try_opt!(a.checked_add(bc), else return Vec::new());
+    vec![sum]
+}
+
+#[test]
+fn try_opt_else_early_return() {
+    assert_eq!(checked_chain_or_default(1, 2, 3), vec![7]);
+    assert_eq!(checked_chain_or_default(u32::MAX, 2, 3), Vec::<u32>::new());
+}
+
+#[test]
+fn try_opt_evaluates_once() {
+    fn next_counter(counter: &mut u32) -> Option<u32> {
+        *counter += 1;
+        Some(*counter)
+    }
+    fn run(counter: &mut u32) -> Option<u32> {
+        let v = try_opt!(next_counter(counter));
+        Some(v)
+    }
+    let mut counter = 0;
+    assert_eq!(run(&mut counter), Some(1));
+    assert_eq!(counter, 1);
+}
+
+#[cfg(test)]
+fn parse_sum(a: &str, b: &str) -> Result<i32, std::num::ParseIntError> {
+    let x = try_res!(a.parse::<i32>());
+    let y = try_res!(b.parse::<i32>());
+    Ok(x + y)
+}
+
+#[test]
+fn try_res_basic() {
+    assert_eq!(parse_sum("1", "2"), Ok(3));
+    assert!(parse_sum("a", "2").is_err());
+}
+
+#[test]
+fn try_res_converts_error_type() {
+    #[derive(Debug, PartialEq)]
+    struct ParseFailed;
+
+    impl From<std::num::ParseIntError> for ParseFailed {
+        fn from(_: std::num::ParseIntError) -> Self {
+            ParseFailed
+        }
+    }
+
+    fn parse_both(a: &str, b: &str) -> Result<i32, ParseFailed> {
+        let x = try_res!(a.parse::<i32>());
+        let y = try_res!(b.parse::<i32>());
+        Ok(x + y)
+    }
+
+    assert_eq!(parse_both("1", "2"), Ok(3));
+    assert_eq!(parse_both("a", "2"), Err(ParseFailed));
+}
+
+/// dispatches on a type specification token tree, reading and parsing however many whitespace
+/// tokens that specification needs from `$it` (an iterator over `&str` tokens)
+///
+/// this is an internal helper for [`input!`] and is not meant to be called directly:
+/// - a bare type parses a single token via `str::parse`
+/// - `[T; n]` collects `n` parsed values into a `Vec<T>` (`n` is only evaluated once)
+/// - `chars` collects the characters of a single token into a `Vec<char>`
+/// - a tuple `(A, B, C)` reads one token per element
+#[macro_export]
+#[doc(hidden)]
+macro_rules! read_value {
+    ($it:ident ; chars) => {
+        $it.next().expect("missing token").chars().collect::<Vec<char>>()
+    };
+    ($it:ident ; [$elem:tt; $n:expr]) => {
+        (0..$n).map(|_| $crate::read_value!($it ; $elem)).collect::<Vec<_>>()
+    };
+    ($it:ident ; ($($t:tt),*)) => {
+        ( $($crate::read_value!($it ; $t)),* )
+    };
+    ($it:ident ; $t:ty) => {
+        $it.next().expect("missing token").parse::<$t>().expect("failed to parse token")
+    };
+}
+
+/// reads whitespace-separated tokens from stdin and binds them to typed variables, competitive
+/// programming style
+///
+/// ```ignore
+/// input! {
+///     n: usize,
+///     xs: [i64; n],
+///     grid: [[u8; m]; n],
+///     s: chars,
+/// }
+/// ```
+///
+/// maintains an internal token iterator over `split_whitespace()` of the buffered input, and
+/// recursively munches `$var:ident : $t:tt` declarations, handing each type specification off to
+/// [`read_value!`]
+///
+/// pass `source = $source` as the first argument to read from an existing `&str` instead of
+/// stdin, which is how this macro is tested without real stdin
+#[macro_export]
+macro_rules! input {
+    // the @parse arms must come before the catch-all entry points below, since `$($rest:tt)*`
+    // would otherwise also swallow our own recursive `@parse` calls
+    (@parse $it:ident ;) => {};
+    (@parse $it:ident ; $var:ident : $t:tt) => {
+        let $var = $crate::read_value!($it ; $t);
+    };
+    (@parse $it:ident ; $var:ident : $t:tt, $($rest:tt)*) => {
+        let $var = $crate::read_value!($it ; $t);
+        $crate::input! { @parse $it ; $($rest)* }
+    };
+    (source = $source:expr, $($rest:tt)*) => {
+        let mut __input_tokens = $source.split_whitespace();
+        $crate::input! { @parse __input_tokens ; $($rest)* }
+    };
+    ($($rest:tt)*) => {
+        let mut __input_buf = String::new();
+        std::io::Read::read_to_string(&mut std::io::stdin(), &mut __input_buf)
+            .expect("failed to read stdin");
+        let mut __input_tokens = __input_buf.split_whitespace();
+        $crate::input! { @parse __input_tokens ; $($rest)* }
+    };
+}
+
+#[test]
+fn input_basic_types() {
+    input! {
+        source = "3 1 2 3",
+        n: usize,
+        xs: [i64; n],
+    }
+    assert_eq!(n, 3);
+    assert_eq!(xs, vec![1, 2, 3]);
+}
+
+#[test]
+fn input_grid() {
+    input! {
+        source = "2 3 1 2 3 4 5 6",
+        n: usize,
+        m: usize,
+        grid: [[u8; m]; n],
+    }
+    assert_eq!(grid, vec![vec![1, 2, 3], vec![4, 5, 6]]);
+}
+
+#[test]
+fn input_chars_and_tuple() {
+    input! {
+        source = "hello 1 2",
+        s: chars,
+        pair: (i32, i32),
+    }
+    assert_eq!(s, vec!['h', 'e', 'l', 'l', 'o']);
+    assert_eq!(pair, (1, 2));
+}
+
+#[test]
+fn count_items_idents() {
+    const N: usize = count_items![A, B, C];
+    assert_eq!(N, 3);
+}
+
+#[test]
+fn count_items_trailing_comma() {
+    const N: usize = count_items![A, B, C,];
+    assert_eq!(N, 3);
+}
+
+#[cfg(test)]
+indexed_enum! { Flags { A, B, C } }
+#[cfg(test)]
+indexed_enum! { Empty {} }
+
+#[test]
+fn indexed_enum_bitmask() {
+    assert_eq!(Flags::A as usize, 1 << 0);
+    assert_eq!(Flags::B as usize, 1 << 1);
+    assert_eq!(Flags::C as usize, 1 << 2);
+}